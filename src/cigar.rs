@@ -0,0 +1,199 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A single CIGAR operation, as used in SAM/GFA alignment strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CIGAROp {
+    Match,
+    Insertion,
+    Deletion,
+    Skip,
+    SoftClip,
+    HardClip,
+    Padding,
+    SeqMatch,
+    SeqMismatch,
+}
+
+impl CIGAROp {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'M' => Some(Self::Match),
+            'I' => Some(Self::Insertion),
+            'D' => Some(Self::Deletion),
+            'N' => Some(Self::Skip),
+            'S' => Some(Self::SoftClip),
+            'H' => Some(Self::HardClip),
+            'P' => Some(Self::Padding),
+            '=' => Some(Self::SeqMatch),
+            'X' => Some(Self::SeqMismatch),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Self::Match => 'M',
+            Self::Insertion => 'I',
+            Self::Deletion => 'D',
+            Self::Skip => 'N',
+            Self::SoftClip => 'S',
+            Self::HardClip => 'H',
+            Self::Padding => 'P',
+            Self::SeqMatch => '=',
+            Self::SeqMismatch => 'X',
+        }
+    }
+
+    /// Whether this operation consumes bases from the query sequence.
+    pub fn consumes_query(self) -> bool {
+        matches!(
+            self,
+            Self::Match | Self::Insertion | Self::SoftClip | Self::SeqMatch | Self::SeqMismatch
+        )
+    }
+
+    /// Whether this operation consumes bases from the reference sequence.
+    pub fn consumes_reference(self) -> bool {
+        matches!(
+            self,
+            Self::Match | Self::Deletion | Self::Skip | Self::SeqMatch | Self::SeqMismatch
+        )
+    }
+}
+
+impl fmt::Display for CIGAROp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// A structured CIGAR alignment: a sequence of (run length, operation) pairs.
+///
+/// Parses the alignment strings found in `Link.overlap`, `Containment.overlap`
+/// and `Path.overlaps`, rather than leaving them as opaque `String`s. The
+/// literal `"*"` parses to an empty/absent `CIGAR`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CIGAR(pub Vec<(u32, CIGAROp)>);
+
+impl CIGAR {
+    /// Number of query bases consumed by this alignment.
+    pub fn query_len(&self) -> u32 {
+        self.0
+            .iter()
+            .filter(|(_, op)| op.consumes_query())
+            .map(|(len, _)| len)
+            .sum()
+    }
+
+    /// Number of reference bases consumed by this alignment.
+    pub fn reference_len(&self) -> u32 {
+        self.0
+            .iter()
+            .filter(|(_, op)| op.consumes_reference())
+            .map(|(len, _)| len)
+            .sum()
+    }
+}
+
+impl FromStr for CIGAR {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "*" {
+            return Ok(CIGAR(Vec::new()));
+        }
+
+        let mut ops = Vec::new();
+        let mut len: Option<u32> = None;
+
+        for c in s.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                let next = len
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or("CIGAR length overflows u32")?;
+                len = Some(next);
+            } else if let Some(op) = CIGAROp::from_char(c) {
+                let len = len.take().ok_or("CIGAR op with no preceding length")?;
+                ops.push((len, op));
+            } else {
+                return Err("unrecognized character in CIGAR string");
+            }
+        }
+
+        if len.is_some() {
+            return Err("trailing CIGAR length with no operation");
+        }
+
+        Ok(CIGAR(ops))
+    }
+}
+
+impl fmt::Display for CIGAR {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "*");
+        }
+        for (len, op) in &self.0 {
+            write!(f, "{}{}", len, op)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cigar() {
+        let cigar: CIGAR = "8M10D2I".parse().unwrap();
+        assert_eq!(
+            cigar.0,
+            vec![
+                (8, CIGAROp::Match),
+                (10, CIGAROp::Deletion),
+                (2, CIGAROp::Insertion),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_absent_overlap() {
+        let cigar: CIGAR = "*".parse().unwrap();
+        assert_eq!(cigar.0, Vec::new());
+    }
+
+    #[test]
+    fn reject_trailing_length() {
+        let result: Result<CIGAR, _> = "8M10".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_op_with_no_length() {
+        let result: Result<CIGAR, _> = "M".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_length_overflowing_u32() {
+        let result: Result<CIGAR, _> = "99999999999999M".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn round_trip_display() {
+        let cigar: CIGAR = "8M10D2I".parse().unwrap();
+        assert_eq!(cigar.to_string(), "8M10D2I");
+    }
+
+    #[test]
+    fn query_and_reference_len() {
+        let cigar: CIGAR = "8M10D2I".parse().unwrap();
+        assert_eq!(cigar.query_len(), 10);
+        assert_eq!(cigar.reference_len(), 18);
+    }
+}
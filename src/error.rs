@@ -0,0 +1,103 @@
+use std::fmt;
+
+/// The kind of field a parser was attempting to read when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Orientation,
+    Integer,
+    Float,
+    Position,
+    CIGAR,
+    OptionalField,
+    SegmentName,
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Orientation => "orientation",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Position => "position",
+            Self::CIGAR => "CIGAR",
+            Self::OptionalField => "optional field",
+            Self::SegmentName => "segment name",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single field that failed to parse while reading a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFieldError {
+    pub line_number: usize,
+    pub line: Vec<u8>,
+    pub expected: FieldType,
+    pub tag: Option<String>,
+}
+
+impl ParseFieldError {
+    pub fn new(line_number: usize, line: &[u8], expected: FieldType, tag: Option<&str>) -> Self {
+        ParseFieldError {
+            line_number,
+            line: line.to_vec(),
+            expected,
+            tag: tag.map(String::from),
+        }
+    }
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {}",
+            self.line_number, self.expected
+        )?;
+        if let Some(tag) = &self.tag {
+            write!(f, " for tag \"{}\"", tag)?;
+        }
+        write!(f, ": {}", String::from_utf8_lossy(&self.line))
+    }
+}
+
+impl std::error::Error for ParseFieldError {}
+
+/// Errors that can occur while parsing a GFA file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A single field on an otherwise recognized line failed to parse.
+    Field(ParseFieldError),
+    /// The line did not start with a recognized line-type tag.
+    UnknownLineType { line_number: usize, line: Vec<u8> },
+    /// The underlying reader failed while reading a line, distinct from a
+    /// clean end of input. Carries `io::Error`'s message rather than the
+    /// error itself, since `io::Error` isn't `Clone`/`Eq`.
+    Io(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(e) => write!(f, "{}", e),
+            Self::UnknownLineType { line_number, line } => write!(
+                f,
+                "line {}: unrecognized line type: {}",
+                line_number,
+                String::from_utf8_lossy(line)
+            ),
+            Self::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseFieldError> for ParseError {
+    fn from(e: ParseFieldError) -> Self {
+        ParseError::Field(e)
+    }
+}
+
+/// Convenience alias for the result of parsing a GFA file or line.
+pub type GFAResult<T> = Result<T, ParseError>;
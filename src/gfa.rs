@@ -1,79 +1,37 @@
+use crate::cigar::CIGAR;
+use crate::gfa2::{Edge, Fragment, Gap, OrderedGroup, UnorderedGroup};
+pub use crate::optfields::{IntArrayType, OptionalField, OptionalFieldValue};
+use crate::segment_id::SegmentId;
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Header {
     pub version: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub enum OptionalFieldValue {
-    PrintableChar(char),
-    SignedInt(i64),
-    Float(f32),
-    PrintableString(String),
-    JSON(String),
-    ByteArray(Vec<u32>),
-    IntArray(Vec<i64>),
-    FloatArray(Vec<f32>),
-}
-
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct OptionalField {
-    pub tag: String,
-    pub content: OptionalFieldValue,
-}
-
-impl OptionalField {
-    pub fn new(tag: &str, content: OptionalFieldValue) -> Self {
-        OptionalField {
-            tag: tag.to_string(),
-            content,
-        }
-    }
+/// Which GFA spec version a file follows, as declared by the header's
+/// `VN:Z:` tag. Gates which line types [`crate::reader::parse_line`] accepts:
+/// `L`/`C`/`P` under `GFA1`, `E`/`G`/`F`/`O`/`U` under `GFA2`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GFAVersion {
+    #[default]
+    GFA1,
+    GFA2,
 }
 
-impl std::fmt::Display for OptionalField {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use OptionalFieldValue::*;
-        write!(f, "{}:", self.tag)?;
-        match &self.content {
-            PrintableChar(c) => write!(f, "A:{}", c),
-            SignedInt(i) => write!(f, "i:{}", i),
-            Float(d) => write!(f, "f:{}", d),
-            PrintableString(s) => write!(f, "Z:{}", s),
-            JSON(s) => write!(f, "J:{}", s),
-            ByteArray(a) => {
-                let mut array_str = String::new();
-                for x in a {
-                    array_str.push(std::char::from_digit(*x, 16).unwrap())
-                }
-                write!(f, "H:{}", array_str)
-            }
-            IntArray(a) => {
-                let mut array_str = String::new();
-                for (i, x) in a.into_iter().enumerate() {
-                    if i > 0 {
-                        array_str.push_str(",");
-                    }
-                    array_str.push_str(&x.to_string());
-                }
-                write!(f, "B:I{}", array_str)
-            }
-            FloatArray(a) => {
-                let mut array_str = String::new();
-                for (i, x) in a.into_iter().enumerate() {
-                    if i > 0 {
-                        array_str.push_str(",");
-                    }
-                    array_str.push_str(&x.to_string());
-                }
-                write!(f, "B:f{}", array_str)
-            }
+impl Header {
+    /// Determines the GFA version from the `VN:Z:` version tag, defaulting
+    /// to GFA1 when the header has no version or an unrecognized one.
+    pub fn gfa_version(&self) -> GFAVersion {
+        match self.version.as_deref() {
+            Some(v) if v.starts_with("2.") => GFAVersion::GFA2,
+            _ => GFAVersion::GFA1,
         }
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
-pub struct Segment {
-    pub name: String,
+pub struct Segment<N: SegmentId> {
+    pub name: N,
     pub sequence: String,
     pub segment_length: Option<i64>,
     pub read_count: Option<i64>,
@@ -84,10 +42,10 @@ pub struct Segment {
     pub optional_fields: Vec<OptionalField>,
 }
 
-impl Segment {
-    pub fn new(name: &str, sequence: &str) -> Self {
+impl<N: SegmentId> Segment<N> {
+    pub fn new(name: N, sequence: &str) -> Self {
         Segment {
-            name: name.to_string(),
+            name,
             sequence: sequence.to_string(),
             ..Default::default()
         }
@@ -139,12 +97,12 @@ impl std::fmt::Display for Orientation {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
-pub struct Link {
-    pub from_segment: String,
+pub struct Link<N: SegmentId> {
+    pub from_segment: N,
     pub from_orient: Orientation,
-    pub to_segment: String,
+    pub to_segment: N,
     pub to_orient: Orientation,
-    pub overlap: String,
+    pub overlap: CIGAR,
     pub map_quality: Option<i64>,
     pub num_mismatches: Option<i64>,
     pub read_count: Option<i64>,
@@ -154,33 +112,33 @@ pub struct Link {
     pub optional_fields: Vec<OptionalField>,
 }
 
-impl Link {
+impl<N: SegmentId> Link<N> {
     pub fn new(
-        from_segment: &str,
+        from_segment: N,
         from_orient: Orientation,
-        to_segment: &str,
+        to_segment: N,
         to_orient: Orientation,
         overlap: &str,
-    ) -> Link {
+    ) -> Link<N> {
         Link {
-            from_segment: from_segment.to_string(),
+            from_segment,
             from_orient,
-            to_segment: to_segment.to_string(),
+            to_segment,
             to_orient,
-            overlap: overlap.to_string(),
+            overlap: overlap.parse().unwrap(),
             ..Default::default()
         }
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
-pub struct Containment {
-    pub container_name: String,
+pub struct Containment<N: SegmentId> {
+    pub container_name: N,
     pub container_orient: Orientation,
-    pub contained_name: String,
+    pub contained_name: N,
     pub contained_orient: Orientation,
     pub pos: usize,
-    pub overlap: String,
+    pub overlap: CIGAR,
     pub read_coverage: Option<i64>,
     pub num_mismatches: Option<i64>,
     pub edge_id: Option<String>,
@@ -188,30 +146,32 @@ pub struct Containment {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
-pub struct Path {
+pub struct Path<N: SegmentId> {
     pub path_name: String,
-    pub segment_names: Vec<(String, Orientation)>,
-    pub overlaps: Vec<String>,
+    pub segment_names: Vec<(N, Orientation)>,
+    pub overlaps: Vec<CIGAR>,
     pub optional_fields: Vec<OptionalField>,
 }
 
-impl Path {
+impl<N: SegmentId> Path<N> {
     pub fn new(
         path_name: &str,
         seg_names: Vec<&str>,
-        overlaps: Vec<String>,
-    ) -> Path {
+        overlaps: Vec<&str>,
+    ) -> Path<N> {
         let segment_names = seg_names
             .iter()
             .map(|s| {
                 let s: &str = s;
                 let (n, o) = s.split_at(s.len() - 1);
-                let name = n.to_string();
+                let name = N::parse_name(n);
                 let orientation = o.parse().unwrap();
                 (name, orientation)
             })
             .collect();
 
+        let overlaps = overlaps.iter().map(|o| o.parse().unwrap()).collect();
+
         Path {
             path_name: path_name.to_string(),
             segment_names,
@@ -222,21 +182,50 @@ impl Path {
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub enum Line {
+pub enum Line<N: SegmentId> {
     Header(Header),
-    Segment(Segment),
-    Link(Link),
-    Containment(Containment),
-    Path(Path),
+    Segment(Segment<N>),
+    Link(Link<N>),
+    Containment(Containment<N>),
+    Path(Path<N>),
+    // GFA2 line types
+    Edge(Edge<N>),
+    Gap(Gap<N>),
+    Fragment(Fragment<N>),
+    OrderedGroup(OrderedGroup<N>),
+    UnorderedGroup(UnorderedGroup),
     Comment,
 }
 
+/// Controls how the parser reacts when a line or field fails to parse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub enum ParserTolerance {
+    /// Abort with an error on the first malformed field.
+    #[default]
+    Pedantic,
+    /// Drop the offending line and keep parsing the rest of the file.
+    Safe,
+    /// Best-effort fill in defaults for malformed fields and keep the line.
+    Lenient,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct GFAParsingConfig {
     pub segments: bool,
     pub links: bool,
     pub containments: bool,
     pub paths: bool,
+    // GFA2 line types
+    pub edges: bool,
+    pub gaps: bool,
+    pub fragments: bool,
+    pub ordered_groups: bool,
+    pub unordered_groups: bool,
+    pub tolerance: ParserTolerance,
+    /// Which line types are in scope; auto-updated from the header's
+    /// declared `VN:Z:` tag by [`crate::reader::GFAReader::next`] once a
+    /// header line is parsed, so callers usually don't need to set this.
+    pub version: GFAVersion,
 }
 
 impl GFAParsingConfig {
@@ -246,6 +235,13 @@ impl GFAParsingConfig {
             links: false,
             containments: false,
             paths: false,
+            edges: false,
+            gaps: false,
+            fragments: false,
+            ordered_groups: false,
+            unordered_groups: false,
+            tolerance: ParserTolerance::default(),
+            version: GFAVersion::default(),
         }
     }
 
@@ -255,21 +251,34 @@ impl GFAParsingConfig {
             links: true,
             containments: true,
             paths: true,
+            edges: true,
+            gaps: true,
+            fragments: true,
+            ordered_groups: true,
+            unordered_groups: true,
+            tolerance: ParserTolerance::default(),
+            version: GFAVersion::default(),
         }
     }
 }
 
 // struct to hold the results of parsing a file; not actually a graph
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
-pub struct GFA {
+pub struct GFA<N: SegmentId> {
     pub version: Option<String>,
-    pub segments: Vec<Segment>,
-    pub links: Vec<Link>,
-    pub containments: Vec<Containment>,
-    pub paths: Vec<Path>,
+    pub segments: Vec<Segment<N>>,
+    pub links: Vec<Link<N>>,
+    pub containments: Vec<Containment<N>>,
+    pub paths: Vec<Path<N>>,
+    // GFA2 line types
+    pub edges: Vec<Edge<N>>,
+    pub gaps: Vec<Gap<N>>,
+    pub fragments: Vec<Fragment<N>>,
+    pub ordered_groups: Vec<OrderedGroup<N>>,
+    pub unordered_groups: Vec<UnorderedGroup>,
 }
 
-impl GFA {
+impl<N: SegmentId> GFA<N> {
     pub fn new() -> Self {
         Default::default()
     }
@@ -283,12 +292,9 @@ mod tests {
     fn create_path() {
         let name = "path1";
         let seg_names = vec!["1+", "2-", "13-", "60+"];
-        let overlaps: Vec<_> = vec!["8M", "10M", "0M", "2M"]
-            .into_iter()
-            .map(String::from)
-            .collect();
+        let overlaps = vec!["8M", "10M", "0M", "2M"];
 
-        let path_expected = Path {
+        let path_expected: Path<String> = Path {
             path_name: name.to_string(),
             segment_names: vec![
                 ("1".to_string(), Orientation::Forward),
@@ -296,12 +302,26 @@ mod tests {
                 ("13".to_string(), Orientation::Backward),
                 ("60".to_string(), Orientation::Forward),
             ],
-            overlaps: overlaps.clone(),
+            overlaps: overlaps.iter().map(|o| o.parse().unwrap()).collect(),
             optional_fields: Vec::new(),
         };
 
-        let path = Path::new(name, seg_names, overlaps);
+        let path: Path<String> = Path::new(name, seg_names, overlaps);
 
         assert_eq!(path, path_expected);
     }
+
+    #[test]
+    fn create_path_numeric_ids() {
+        let name = "path1";
+        let seg_names = vec!["1+", "2-"];
+        let overlaps = vec!["8M", "10M"];
+
+        let path: Path<usize> = Path::new(name, seg_names, overlaps);
+
+        assert_eq!(
+            path.segment_names,
+            vec![(1, Orientation::Forward), (2, Orientation::Backward)]
+        );
+    }
 }
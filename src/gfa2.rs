@@ -0,0 +1,121 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cigar::CIGAR;
+use crate::gfa::{Orientation, OptionalField};
+use crate::segment_id::SegmentId;
+
+/// A coordinate in a GFA2 alignment. May be terminated with `$` to mean
+/// "the end of the segment" rather than a literal offset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub pos: u64,
+    pub is_end: bool,
+}
+
+impl FromStr for Position {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, is_end) = match s.strip_suffix('$') {
+            Some(d) => (d, true),
+            None => (s, false),
+        };
+        let pos = digits.parse().map_err(|_| "expected an integer position")?;
+        Ok(Position { pos, is_end })
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pos)?;
+        if self.is_end {
+            write!(f, "$")?;
+        }
+        Ok(())
+    }
+}
+
+/// A GFA2 `E` line: an edge between two oriented segment intervals.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Edge<N: SegmentId> {
+    pub id: Option<String>,
+    pub sid1: N,
+    pub sid1_orient: Orientation,
+    pub sid2: N,
+    pub sid2_orient: Orientation,
+    pub beg1: Position,
+    pub end1: Position,
+    pub beg2: Position,
+    pub end2: Position,
+    pub overlap: CIGAR,
+    pub optional_fields: Vec<OptionalField>,
+}
+
+/// A GFA2 `G` line: an asserted gap of known distance between two segments.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Gap<N: SegmentId> {
+    pub id: Option<String>,
+    pub sid1: N,
+    pub sid1_orient: Orientation,
+    pub sid2: N,
+    pub sid2_orient: Orientation,
+    pub distance: i64,
+    pub variance: Option<i64>,
+    pub optional_fields: Vec<OptionalField>,
+}
+
+/// A GFA2 `F` line: placement of an external read within a segment.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Fragment<N: SegmentId> {
+    pub sid: N,
+    pub external_id: String,
+    pub sbeg: Position,
+    pub send: Position,
+    pub fbeg: Position,
+    pub fend: Position,
+    pub alignment: Option<CIGAR>,
+    pub optional_fields: Vec<OptionalField>,
+}
+
+/// A GFA2 `O` line: an ordered group of oriented references, generalizing
+/// GFA1's `Path`.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct OrderedGroup<N: SegmentId> {
+    pub id: Option<String>,
+    pub items: Vec<(N, Orientation)>,
+    pub optional_fields: Vec<OptionalField>,
+}
+
+/// A GFA2 `U` line: an unordered group of segment/edge/group ids, with no
+/// implied orientation or order. Ids of mixed kinds (segment, edge, or
+/// nested group) are all valid members, so they're kept as raw strings
+/// rather than the graph's `SegmentId` type.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct UnorderedGroup {
+    pub id: Option<String>,
+    pub items: Vec<String>,
+    pub optional_fields: Vec<OptionalField>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_position() {
+        assert_eq!(
+            "42".parse::<Position>().unwrap(),
+            Position { pos: 42, is_end: false }
+        );
+        assert_eq!(
+            "42$".parse::<Position>().unwrap(),
+            Position { pos: 42, is_end: true }
+        );
+    }
+
+    #[test]
+    fn reject_non_numeric_position() {
+        assert!("abc".parse::<Position>().is_err());
+    }
+}
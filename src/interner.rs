@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+/// Interns segment names into small integer ids, keeping a side table to
+/// recover the original names for output.
+///
+/// Used by [`crate::segment_id::SegmentId::intern`] when [`crate::reader`]
+/// parses a non-numeric segment name into `GFA<usize>`/`GFA<u64>` rather
+/// than `GFA<String>` (a numeric name keeps its literal value instead, so a
+/// file that's already all-numeric never touches the interner). A single
+/// interner is shared across an entire file via [`crate::reader::GFAReader`]
+/// so the same name always maps to the same id.
+///
+/// Every id this returns has the highest bit of `usize` set, which a literal
+/// numeric segment name can only reach by being `>= 2^63`. That keeps
+/// interned ids disjoint from literal ones even in a graph that mixes
+/// numeric and non-numeric segment names, short of that implausibly large
+/// literal id.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentInterner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+/// Tags an id as interner-assigned rather than a literal numeric segment
+/// name; see [`SegmentInterner`]'s doc comment.
+const TAG_BIT: usize = 1 << (usize::BITS - 1);
+
+impl SegmentInterner {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the id for `name`, assigning a new one if it hasn't been seen.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len() | TAG_BIT;
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Looks up the original name for a previously interned id.
+    pub fn name(&self, id: usize) -> Option<&str> {
+        self.names.get(id & !TAG_BIT).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_reuses_ids_for_repeated_names() {
+        let mut interner = SegmentInterner::new();
+        let a = interner.intern("seg1");
+        let b = interner.intern("seg2");
+        let a_again = interner.intern("seg1");
+
+        assert_eq!(a, a_again);
+        assert_ne!(a, b);
+        assert_eq!(interner.name(a), Some("seg1"));
+        assert_eq!(interner.name(b), Some("seg2"));
+    }
+
+    #[test]
+    fn interned_ids_never_collide_with_small_literal_numeric_ids() {
+        let mut interner = SegmentInterner::new();
+        let a = interner.intern("seg1");
+        let b = interner.intern("seg2");
+
+        assert_ne!(a, 0);
+        assert_ne!(b, 1);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod cigar;
+pub mod error;
+pub mod gfa;
+pub mod gfa2;
+pub mod interner;
+pub mod optfields;
+pub mod reader;
+pub mod segment_id;
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use crate::error::{FieldType, ParseFieldError};
+
+/// The subtype letter of a `B` (typed numeric array) optional field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntArrayType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+}
+
+impl IntArrayType {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'c' => Some(Self::I8),
+            'C' => Some(Self::U8),
+            's' => Some(Self::I16),
+            'S' => Some(Self::U16),
+            'i' => Some(Self::I32),
+            'I' => Some(Self::U32),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Self::I8 => 'c',
+            Self::U8 => 'C',
+            Self::I16 => 's',
+            Self::U16 => 'S',
+            Self::I32 => 'i',
+            Self::U32 => 'I',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum OptionalFieldValue {
+    PrintableChar(char),
+    SignedInt(i64),
+    Float(f32),
+    PrintableString(String),
+    JSON(String),
+    ByteArray(Vec<u8>),
+    IntArray(IntArrayType, Vec<i64>),
+    FloatArray(Vec<f32>),
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct OptionalField {
+    pub tag: String,
+    pub content: OptionalFieldValue,
+}
+
+impl OptionalField {
+    pub fn new(tag: &str, content: OptionalFieldValue) -> Self {
+        OptionalField {
+            tag: tag.to_string(),
+            content,
+        }
+    }
+
+    /// Parses a single `TAG:TYPE:VALUE` optional field, validating that the
+    /// tag follows the `[A-Za-z][A-Za-z0-9]` rule and that the type letter
+    /// matches the decoded value.
+    ///
+    /// `line_number` and `line` are only used to annotate the returned
+    /// error, so callers can report where in the file the field came from.
+    pub fn parse(line_number: usize, line: &[u8], field: &str) -> Result<Self, ParseFieldError> {
+        let err = || ParseFieldError::new(line_number, line, FieldType::OptionalField, None);
+        let tag_err =
+            |tag: &str| ParseFieldError::new(line_number, line, FieldType::OptionalField, Some(tag));
+
+        let mut parts = field.splitn(3, ':');
+        let tag = parts.next().ok_or_else(err)?;
+        let type_char = parts.next().ok_or_else(|| tag_err(tag))?;
+        let value = parts.next().ok_or_else(|| tag_err(tag))?;
+
+        if !is_valid_tag(tag) {
+            return Err(tag_err(tag));
+        }
+
+        let content = match type_char {
+            "A" => {
+                let mut chars = value.chars();
+                let c = chars.next().filter(|c| c.is_ascii_graphic());
+                match (c, chars.next()) {
+                    (Some(c), None) => OptionalFieldValue::PrintableChar(c),
+                    _ => return Err(tag_err(tag)),
+                }
+            }
+            "i" => OptionalFieldValue::SignedInt(value.parse().map_err(|_| tag_err(tag))?),
+            "f" => OptionalFieldValue::Float(value.parse().map_err(|_| tag_err(tag))?),
+            "Z" => OptionalFieldValue::PrintableString(value.to_string()),
+            "J" => OptionalFieldValue::JSON(value.to_string()),
+            "H" => OptionalFieldValue::ByteArray(parse_hex_bytes(value).ok_or_else(|| tag_err(tag))?),
+            "B" => parse_typed_array(value).ok_or_else(|| tag_err(tag))?,
+            _ => return Err(tag_err(tag)),
+        };
+
+        Ok(OptionalField::new(tag, content))
+    }
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    let mut chars = tag.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(a), Some(b), None) => {
+            a.is_ascii_alphabetic() && (b.is_ascii_alphabetic() || b.is_ascii_digit())
+        }
+        _ => false,
+    }
+}
+
+fn parse_hex_bytes(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(value.len() / 2);
+    let chars: Vec<char> = value.chars().collect();
+    for pair in chars.chunks(2) {
+        let hi = pair[0].to_digit(16)?;
+        let lo = pair[1].to_digit(16)?;
+        bytes.push((hi * 16 + lo) as u8);
+    }
+    Some(bytes)
+}
+
+fn parse_typed_array(value: &str) -> Option<OptionalFieldValue> {
+    let mut chars = value.chars();
+    let subtype = chars.next()?;
+    let rest = chars.as_str();
+    let items: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        rest.split(',').collect()
+    };
+
+    if subtype == 'f' {
+        let floats: Option<Vec<f32>> = items.iter().map(|s| s.parse().ok()).collect();
+        return Some(OptionalFieldValue::FloatArray(floats?));
+    }
+
+    let array_type = IntArrayType::from_char(subtype)?;
+    let ints: Option<Vec<i64>> = items.iter().map(|s| s.parse().ok()).collect();
+    Some(OptionalFieldValue::IntArray(array_type, ints?))
+}
+
+impl fmt::Display for OptionalField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use OptionalFieldValue::*;
+        write!(f, "{}:", self.tag)?;
+        match &self.content {
+            PrintableChar(c) => write!(f, "A:{}", c),
+            SignedInt(i) => write!(f, "i:{}", i),
+            Float(d) => write!(f, "f:{}", d),
+            PrintableString(s) => write!(f, "Z:{}", s),
+            JSON(s) => write!(f, "J:{}", s),
+            ByteArray(a) => {
+                let mut array_str = String::new();
+                for byte in a {
+                    array_str.push_str(&format!("{:02X}", byte));
+                }
+                write!(f, "H:{}", array_str)
+            }
+            IntArray(array_type, a) => {
+                let mut array_str = String::new();
+                for (i, x) in a.iter().enumerate() {
+                    if i > 0 {
+                        array_str.push(',');
+                    }
+                    array_str.push_str(&x.to_string());
+                }
+                write!(f, "B:{}{}", array_type.to_char(), array_str)
+            }
+            FloatArray(a) => {
+                let mut array_str = String::new();
+                for (i, x) in a.iter().enumerate() {
+                    if i > 0 {
+                        array_str.push(',');
+                    }
+                    array_str.push_str(&x.to_string());
+                }
+                write!(f, "B:f{}", array_str)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_signed_int_field() {
+        let field = OptionalField::parse(1, b"", "RC:i:42").unwrap();
+        assert_eq!(field.tag, "RC");
+        assert_eq!(field.content, OptionalFieldValue::SignedInt(42));
+    }
+
+    #[test]
+    fn reject_invalid_tag() {
+        assert!(OptionalField::parse(1, b"", "1C:i:42").is_err());
+    }
+
+    #[test]
+    fn reject_mismatched_type() {
+        assert!(OptionalField::parse(1, b"", "RC:i:not_a_number").is_err());
+    }
+
+    #[test]
+    fn parse_hex_byte_array() {
+        let field = OptionalField::parse(1, b"", "HH:H:1AFF").unwrap();
+        assert_eq!(field.content, OptionalFieldValue::ByteArray(vec![0x1A, 0xFF]));
+    }
+
+    #[test]
+    fn reject_odd_length_hex() {
+        assert!(OptionalField::parse(1, b"", "HH:H:1A0").is_err());
+    }
+
+    #[test]
+    fn reject_non_hex_byte_array() {
+        assert!(OptionalField::parse(1, b"", "HH:H:ZZ").is_err());
+    }
+
+    #[test]
+    fn parse_typed_int_array() {
+        let field = OptionalField::parse(1, b"", "XA:B:i1,2,3").unwrap();
+        assert_eq!(
+            field.content,
+            OptionalFieldValue::IntArray(IntArrayType::I32, vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn parse_typed_float_array() {
+        let field = OptionalField::parse(1, b"", "XA:B:f1.5,2.5").unwrap();
+        assert_eq!(
+            field.content,
+            OptionalFieldValue::FloatArray(vec![1.5, 2.5])
+        );
+    }
+
+    #[test]
+    fn round_trip_display() {
+        let field = OptionalField::parse(1, b"", "HH:H:1AFF").unwrap();
+        assert_eq!(field.to_string(), "HH:H:1AFF");
+    }
+}
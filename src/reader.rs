@@ -0,0 +1,1077 @@
+//! Zero-copy line parsing and a streaming reader for large GFA files.
+//!
+//! Unlike the builder methods on [`crate::gfa`]'s types (which assume valid
+//! input and panic on a bad field), everything here is fallible: a
+//! malformed field is reported through [`GFAResult`] rather than a panic,
+//! so callers can apply [`ParserTolerance`] instead of aborting the whole
+//! file.
+
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+use crate::cigar::CIGAR;
+use crate::error::{FieldType, ParseError, ParseFieldError};
+use crate::gfa::{
+    Containment, GFAParsingConfig, GFAVersion, Header, Line, Link, Orientation, ParserTolerance,
+    Path, Segment,
+};
+use crate::gfa2::{Edge, Fragment, Gap, OrderedGroup, Position, UnorderedGroup};
+use crate::error::GFAResult;
+use crate::interner::SegmentInterner;
+use crate::optfields::{OptionalField, OptionalFieldValue};
+use crate::segment_id::SegmentId;
+
+fn field_err(line_number: usize, raw: &[u8], expected: FieldType) -> ParseError {
+    ParseFieldError::new(line_number, raw, expected, None).into()
+}
+
+fn field_str<'a>(
+    line_number: usize,
+    raw: &[u8],
+    field: Option<&'a [u8]>,
+    expected: FieldType,
+) -> GFAResult<&'a str> {
+    let field = field.ok_or_else(|| field_err(line_number, raw, expected))?;
+    std::str::from_utf8(field).map_err(|_| field_err(line_number, raw, expected))
+}
+
+fn parse_field<T: std::str::FromStr>(
+    line_number: usize,
+    raw: &[u8],
+    field: Option<&[u8]>,
+    expected: FieldType,
+) -> GFAResult<T> {
+    let s = field_str(line_number, raw, field, expected)?;
+    s.parse().map_err(|_| field_err(line_number, raw, expected))
+}
+
+fn parse_oriented_id<N: SegmentId>(s: &str, interner: &mut SegmentInterner) -> Option<(N, Orientation)> {
+    if s.len() < 2 || !s.is_char_boundary(s.len() - 1) {
+        return None;
+    }
+    let (name, orient) = s.split_at(s.len() - 1);
+    Some((N::intern(name, interner), orient.parse().ok()?))
+}
+
+fn parse_alignment(line_number: usize, raw: &[u8], s: &str) -> GFAResult<Option<CIGAR>> {
+    if s == "*" {
+        return Ok(None);
+    }
+    s.parse()
+        .map(Some)
+        .map_err(|_| field_err(line_number, raw, FieldType::CIGAR))
+}
+
+/// Parses the trailing tab-separated fields of a line as optional fields.
+///
+/// Under [`ParserTolerance::Lenient`], a field that fails to parse is
+/// dropped rather than aborting the whole line; under `Pedantic` or `Safe`
+/// it's propagated as an error (`Safe` line-dropping happens one level up,
+/// in [`GFAReader`]).
+fn collect_optional_fields<'a>(
+    line_number: usize,
+    raw: &[u8],
+    fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+) -> GFAResult<Vec<OptionalField>> {
+    let mut result = Vec::new();
+    for field in fields {
+        let s = match field_str(line_number, raw, Some(field), FieldType::OptionalField) {
+            Ok(s) => s,
+            Err(_) if tolerance == ParserTolerance::Lenient => continue,
+            Err(e) => return Err(e),
+        };
+        match OptionalField::parse(line_number, raw, s) {
+            Ok(of) => result.push(of),
+            Err(e) => {
+                if tolerance != ParserTolerance::Lenient {
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Falls back to `default()` under [`ParserTolerance::Lenient`] instead of
+/// propagating `result`'s error, so a line with a malformed required field
+/// still yields a usable (if approximate) value rather than dropping the
+/// whole line. Under `Pedantic`/`Safe`, the error is passed through
+/// unchanged (`Safe` line-dropping happens one level up, in [`GFAReader`]).
+fn lenient<T>(
+    tolerance: ParserTolerance,
+    default: impl FnOnce() -> T,
+    result: GFAResult<T>,
+) -> GFAResult<T> {
+    match result {
+        Ok(v) => Ok(v),
+        Err(_) if tolerance == ParserTolerance::Lenient => Ok(default()),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse_segment<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Segment<N>> {
+    let name = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let sequence = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let name = N::intern(name, interner);
+
+    let mut segment = Segment::new(name, sequence);
+    for opt in collect_optional_fields(line_number, raw, fields, tolerance)? {
+        match (opt.tag.as_str(), &opt.content) {
+            ("LN", OptionalFieldValue::SignedInt(v)) => segment.segment_length = Some(*v),
+            ("RC", OptionalFieldValue::SignedInt(v)) => segment.read_count = Some(*v),
+            ("FC", OptionalFieldValue::SignedInt(v)) => segment.fragment_count = Some(*v),
+            ("KC", OptionalFieldValue::SignedInt(v)) => segment.kmer_count = Some(*v),
+            ("UR", OptionalFieldValue::PrintableString(v)) => segment.uri = Some(v.clone()),
+            ("SH", OptionalFieldValue::ByteArray(bytes)) => {
+                segment.sha256 = Some(bytes.iter().map(|b| *b as u32).collect())
+            }
+            _ => segment.optional_fields.push(opt),
+        }
+    }
+    Ok(segment)
+}
+
+fn parse_link<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Link<N>> {
+    let from_segment = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let from_orient: Orientation = lenient(
+        tolerance,
+        Orientation::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Orientation),
+    )?;
+    let to_segment = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let to_orient: Orientation = lenient(
+        tolerance,
+        Orientation::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Orientation),
+    )?;
+    let overlap = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::CIGAR),
+    )?;
+
+    let from_segment = N::intern(from_segment, interner);
+    let to_segment = N::intern(to_segment, interner);
+    let overlap = lenient(tolerance, || None, parse_alignment(line_number, raw, overlap))?.unwrap_or_default();
+
+    let mut link = Link {
+        from_segment,
+        from_orient,
+        to_segment,
+        to_orient,
+        overlap,
+        ..Default::default()
+    };
+
+    for opt in collect_optional_fields(line_number, raw, fields, tolerance)? {
+        match (opt.tag.as_str(), &opt.content) {
+            ("MQ", OptionalFieldValue::SignedInt(v)) => link.map_quality = Some(*v),
+            ("NM", OptionalFieldValue::SignedInt(v)) => link.num_mismatches = Some(*v),
+            ("RC", OptionalFieldValue::SignedInt(v)) => link.read_count = Some(*v),
+            ("FC", OptionalFieldValue::SignedInt(v)) => link.fragment_count = Some(*v),
+            ("KC", OptionalFieldValue::SignedInt(v)) => link.kmer_count = Some(*v),
+            ("ID", OptionalFieldValue::PrintableString(v)) => link.edge_id = Some(v.clone()),
+            _ => link.optional_fields.push(opt),
+        }
+    }
+    Ok(link)
+}
+
+fn parse_containment<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Containment<N>> {
+    let container_name = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let container_orient = lenient(
+        tolerance,
+        Orientation::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Orientation),
+    )?;
+    let contained_name = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let contained_orient = lenient(
+        tolerance,
+        Orientation::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Orientation),
+    )?;
+    let pos = lenient(
+        tolerance,
+        || 0usize,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let overlap = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::CIGAR),
+    )?;
+    let overlap = lenient(tolerance, || None, parse_alignment(line_number, raw, overlap))?.unwrap_or_default();
+
+    let container_name = N::intern(container_name, interner);
+    let contained_name = N::intern(contained_name, interner);
+
+    let mut containment = Containment {
+        container_name,
+        container_orient,
+        contained_name,
+        contained_orient,
+        pos,
+        overlap,
+        ..Default::default()
+    };
+
+    for opt in collect_optional_fields(line_number, raw, fields, tolerance)? {
+        match (opt.tag.as_str(), &opt.content) {
+            ("RC", OptionalFieldValue::SignedInt(v)) => containment.read_coverage = Some(*v),
+            ("NM", OptionalFieldValue::SignedInt(v)) => containment.num_mismatches = Some(*v),
+            ("ID", OptionalFieldValue::PrintableString(v)) => containment.edge_id = Some(v.clone()),
+            _ => containment.optional_fields.push(opt),
+        }
+    }
+    Ok(containment)
+}
+
+fn parse_path<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Path<N>> {
+    let path_name = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let seg_names_field = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let overlaps_field = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::CIGAR),
+    )?;
+
+    let seg_names: Vec<&str> = seg_names_field.split(',').collect();
+    let overlaps: Vec<&str> = if overlaps_field == "*" {
+        vec!["*"; seg_names.len()]
+    } else {
+        overlaps_field.split(',').collect()
+    };
+
+    let segment_names = seg_names
+        .iter()
+        .map(|s| {
+            lenient(
+                tolerance,
+                || (N::default(), Orientation::default()),
+                parse_oriented_id::<N>(s, interner)
+                    .ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+            )
+        })
+        .collect::<GFAResult<Vec<_>>>()?;
+    let parsed_overlaps = overlaps
+        .iter()
+        .map(|o| lenient(tolerance, || None, parse_alignment(line_number, raw, o)).map(Option::unwrap_or_default))
+        .collect::<GFAResult<Vec<_>>>()?;
+
+    let mut path = Path {
+        path_name: path_name.to_string(),
+        segment_names,
+        overlaps: parsed_overlaps,
+        optional_fields: Vec::new(),
+    };
+    path.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(path)
+}
+
+fn parse_edge<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Edge<N>> {
+    let id = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let sid1 = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let sid2 = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let beg1: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let end1: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let beg2: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let end2: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let overlap = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::CIGAR),
+    )?;
+
+    let (sid1, sid1_orient) = lenient(
+        tolerance,
+        || (N::default(), Orientation::default()),
+        parse_oriented_id::<N>(sid1, interner).ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+    )?;
+    let (sid2, sid2_orient) = lenient(
+        tolerance,
+        || (N::default(), Orientation::default()),
+        parse_oriented_id::<N>(sid2, interner).ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+    )?;
+    let overlap = lenient(tolerance, || None, parse_alignment(line_number, raw, overlap))?.unwrap_or_default();
+
+    let mut edge = Edge {
+        id: (id != "*").then(|| id.to_string()),
+        sid1,
+        sid1_orient,
+        sid2,
+        sid2_orient,
+        beg1,
+        end1,
+        beg2,
+        end2,
+        overlap,
+        optional_fields: Vec::new(),
+    };
+    edge.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(edge)
+}
+
+fn parse_gap<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Gap<N>> {
+    let id = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let sid1 = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let sid2 = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let distance = lenient(
+        tolerance,
+        || 0i64,
+        parse_field(line_number, raw, fields.next(), FieldType::Integer),
+    )?;
+    let variance_field = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::Integer),
+    )?;
+
+    let (sid1, sid1_orient) = lenient(
+        tolerance,
+        || (N::default(), Orientation::default()),
+        parse_oriented_id::<N>(sid1, interner).ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+    )?;
+    let (sid2, sid2_orient) = lenient(
+        tolerance,
+        || (N::default(), Orientation::default()),
+        parse_oriented_id::<N>(sid2, interner).ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+    )?;
+    let variance = if variance_field == "*" {
+        None
+    } else {
+        lenient(
+            tolerance,
+            || None,
+            variance_field
+                .parse()
+                .map(Some)
+                .map_err(|_| field_err(line_number, raw, FieldType::Integer)),
+        )?
+    };
+
+    let mut gap = Gap {
+        id: (id != "*").then(|| id.to_string()),
+        sid1,
+        sid1_orient,
+        sid2,
+        sid2_orient,
+        distance,
+        variance,
+        optional_fields: Vec::new(),
+    };
+    gap.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(gap)
+}
+
+fn parse_fragment<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Fragment<N>> {
+    let sid = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let external_id = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?
+    .to_string();
+    let sbeg: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let send: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let fbeg: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let fend: Position = lenient(
+        tolerance,
+        Position::default,
+        parse_field(line_number, raw, fields.next(), FieldType::Position),
+    )?;
+    let alignment = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::CIGAR),
+    )?;
+
+    let sid = N::intern(sid, interner);
+    let alignment = lenient(tolerance, || None, parse_alignment(line_number, raw, alignment))?;
+
+    let mut fragment = Fragment {
+        sid,
+        external_id,
+        sbeg,
+        send,
+        fbeg,
+        fend,
+        alignment,
+        optional_fields: Vec::new(),
+    };
+    fragment.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(fragment)
+}
+
+fn parse_ordered_group<'a, N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+    interner: &mut SegmentInterner,
+) -> GFAResult<OrderedGroup<N>> {
+    let id = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let items_field = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+
+    let items = items_field
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            lenient(
+                tolerance,
+                || (N::default(), Orientation::default()),
+                parse_oriented_id::<N>(s, interner)
+                    .ok_or_else(|| field_err(line_number, raw, FieldType::SegmentName)),
+            )
+        })
+        .collect::<GFAResult<Vec<_>>>()?;
+
+    let mut group = OrderedGroup {
+        id: (id != "*").then(|| id.to_string()),
+        items,
+        optional_fields: Vec::new(),
+    };
+    group.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(group)
+}
+
+fn parse_unordered_group<'a>(
+    line_number: usize,
+    raw: &[u8],
+    mut fields: impl Iterator<Item = &'a [u8]>,
+    tolerance: ParserTolerance,
+) -> GFAResult<UnorderedGroup> {
+    let id = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let items_field = lenient(
+        tolerance,
+        || "*",
+        field_str(line_number, raw, fields.next(), FieldType::SegmentName),
+    )?;
+    let items = items_field
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+
+    let mut group = UnorderedGroup {
+        id: (id != "*").then(|| id.to_string()),
+        items,
+        optional_fields: Vec::new(),
+    };
+    group.optional_fields = collect_optional_fields(line_number, raw, fields, tolerance)?;
+    Ok(group)
+}
+
+/// Parses a single GFA line from a byte slice, without requiring it (or its
+/// fields) to be valid UTF-8 up front — only the fields actually read are
+/// validated, and tab-splitting never allocates.
+///
+/// `interner` assigns integer ids to non-numeric segment names when `N`
+/// isn't `String`; see [`crate::segment_id::SegmentId::intern`].
+///
+/// Returns `Ok(Some(Line::Comment))` for comment lines, and `Ok(None)` for
+/// line types disabled in `config`.
+pub fn parse_line<N: SegmentId>(
+    line_number: usize,
+    raw: &[u8],
+    config: &GFAParsingConfig,
+    interner: &mut SegmentInterner,
+) -> GFAResult<Option<Line<N>>> {
+    if raw.is_empty() || raw[0] == b'#' {
+        return Ok(Some(Line::Comment));
+    }
+
+    let mut fields = raw.split(|&b| b == b'\t');
+    let tag = fields.next().unwrap_or(b"");
+    let tolerance = config.tolerance;
+
+    match tag {
+        b"H" => {
+            let mut version = None;
+            for opt in collect_optional_fields(line_number, raw, fields, tolerance)? {
+                if opt.tag == "VN" {
+                    if let OptionalFieldValue::PrintableString(v) = &opt.content {
+                        version = Some(v.clone());
+                    }
+                }
+            }
+            Ok(Some(Line::Header(Header { version })))
+        }
+        b"S" if config.segments => {
+            parse_segment(line_number, raw, fields, tolerance, interner).map(|s| Some(Line::Segment(s)))
+        }
+        b"L" if config.links && config.version == GFAVersion::GFA1 => {
+            parse_link(line_number, raw, fields, tolerance, interner).map(|l| Some(Line::Link(l)))
+        }
+        b"C" if config.containments && config.version == GFAVersion::GFA1 => {
+            parse_containment(line_number, raw, fields, tolerance, interner).map(|c| Some(Line::Containment(c)))
+        }
+        b"P" if config.paths && config.version == GFAVersion::GFA1 => {
+            parse_path(line_number, raw, fields, tolerance, interner).map(|p| Some(Line::Path(p)))
+        }
+        b"E" if config.edges && config.version == GFAVersion::GFA2 => {
+            parse_edge(line_number, raw, fields, tolerance, interner).map(|e| Some(Line::Edge(e)))
+        }
+        b"G" if config.gaps && config.version == GFAVersion::GFA2 => {
+            parse_gap(line_number, raw, fields, tolerance, interner).map(|g| Some(Line::Gap(g)))
+        }
+        b"F" if config.fragments && config.version == GFAVersion::GFA2 => {
+            parse_fragment(line_number, raw, fields, tolerance, interner).map(|f| Some(Line::Fragment(f)))
+        }
+        b"O" if config.ordered_groups && config.version == GFAVersion::GFA2 => {
+            parse_ordered_group(line_number, raw, fields, tolerance, interner).map(|g| Some(Line::OrderedGroup(g)))
+        }
+        b"U" if config.unordered_groups && config.version == GFAVersion::GFA2 => {
+            parse_unordered_group(line_number, raw, fields, tolerance).map(|g| Some(Line::UnorderedGroup(g)))
+        }
+        b"S" | b"L" | b"C" | b"P" | b"E" | b"G" | b"F" | b"O" | b"U" => Ok(None),
+        _ => Err(ParseError::UnknownLineType {
+            line_number,
+            line: raw.to_vec(),
+        }),
+    }
+}
+
+/// Streams [`Line`]s out of a [`BufRead`] source one at a time, so callers
+/// can process graphs far larger than memory without ever materializing a
+/// whole [`crate::gfa::GFA`].
+///
+/// Lines are read as raw bytes (via `read_until(b'\n', ..)`), so no UTF-8
+/// validation happens until an individual field is actually parsed. Comment
+/// lines are skipped rather than yielded. Honors `config`'s
+/// [`ParserTolerance`]: `Safe`/`Lenient` silently skip malformed lines
+/// instead of yielding an error. A single [`SegmentInterner`] is shared
+/// across the whole file, so repeated segment names always map to the same
+/// integer id when `N` is `usize`/`u64`.
+pub struct GFAReader<R: BufRead, N: SegmentId> {
+    reader: R,
+    buf: Vec<u8>,
+    line_number: usize,
+    config: GFAParsingConfig,
+    interner: SegmentInterner,
+    _marker: PhantomData<N>,
+}
+
+impl<R: BufRead, N: SegmentId> GFAReader<R, N> {
+    pub fn new(reader: R, config: GFAParsingConfig) -> Self {
+        GFAReader {
+            reader,
+            buf: Vec::new(),
+            line_number: 0,
+            config,
+            interner: SegmentInterner::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The interner accumulating segment-name-to-id assignments for this
+    /// reader. Only meaningful when `N` is `usize`/`u64`; `GFA<String>`
+    /// readers never assign ids and leave it empty.
+    pub fn interner(&self) -> &SegmentInterner {
+        &self.interner
+    }
+}
+
+impl<R: BufRead, N: SegmentId> Iterator for GFAReader<R, N> {
+    type Item = GFAResult<Line<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buf.clear();
+            let bytes_read = match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(n) => n,
+                Err(e) => return Some(Err(ParseError::Io(e.to_string()))),
+            };
+            if bytes_read == 0 {
+                return None;
+            }
+            self.line_number += 1;
+
+            while matches!(self.buf.last(), Some(b'\n') | Some(b'\r')) {
+                self.buf.pop();
+            }
+
+            match parse_line::<N>(self.line_number, &self.buf, &self.config, &mut self.interner) {
+                Ok(Some(Line::Comment)) => continue,
+                Ok(Some(Line::Header(header))) => {
+                    self.config.version = header.gfa_version();
+                    return Some(Ok(Line::Header(header)));
+                }
+                Ok(Some(line)) => return Some(Ok(line)),
+                Ok(None) => continue,
+                Err(e) => match self.config.tolerance {
+                    ParserTolerance::Pedantic => return Some(Err(e)),
+                    ParserTolerance::Safe | ParserTolerance::Lenient => continue,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gfa::GFAParsingConfig;
+    use std::io::Cursor;
+
+    fn interner() -> SegmentInterner {
+        SegmentInterner::new()
+    }
+
+    #[test]
+    fn parse_segment_line() {
+        let config = GFAParsingConfig::all();
+        let line = parse_line::<String>(1, b"S\t11\tACCTT\tLN:i:5", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::Segment(s) => {
+                assert_eq!(s.name, "11");
+                assert_eq!(s.sequence, "ACCTT");
+                assert_eq!(s.segment_length, Some(5));
+            }
+            other => panic!("expected Segment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn oriented_id_ending_in_multi_byte_char_is_an_error_not_a_panic() {
+        let config = GFAParsingConfig::all();
+        let result = parse_line::<String>(1, b"P\tpath1\t12\xc3\xa9\t*", &config, &mut interner());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_link_line_numeric_ids() {
+        let config = GFAParsingConfig::all();
+        let line = parse_line::<usize>(1, b"L\t11\t+\t12\t-\t4M", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::Link(l) => {
+                assert_eq!(l.from_segment, 11);
+                assert_eq!(l.to_segment, 12);
+                assert_eq!(l.from_orient, Orientation::Forward);
+                assert_eq!(l.to_orient, Orientation::Backward);
+            }
+            other => panic!("expected Link, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn disabled_line_type_is_filtered() {
+        let config = GFAParsingConfig::none();
+        let line = parse_line::<String>(1, b"S\t11\tACCTT", &config, &mut interner()).unwrap();
+        assert!(line.is_none());
+    }
+
+    #[test]
+    fn unknown_line_type_is_an_error() {
+        let config = GFAParsingConfig::all();
+        let result = parse_line::<String>(1, b"Q\tsomething", &config, &mut interner());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn non_numeric_segment_names_are_interned_to_the_same_id() {
+        let config = GFAParsingConfig::all();
+        let mut interner = interner();
+
+        let first = parse_line::<usize>(1, b"S\tchr1\tACCTT", &config, &mut interner)
+            .unwrap()
+            .unwrap();
+        let second = parse_line::<usize>(2, b"S\tchr2\tTTTTT", &config, &mut interner)
+            .unwrap()
+            .unwrap();
+        let third = parse_line::<usize>(3, b"S\tchr1\tGGGGG", &config, &mut interner)
+            .unwrap()
+            .unwrap();
+
+        let id = |line: &Line<usize>| match line {
+            Line::Segment(s) => s.name,
+            other => panic!("expected Segment, got {:?}", other),
+        };
+
+        assert_eq!(id(&first), id(&third));
+        assert_ne!(id(&first), id(&second));
+        assert_eq!(interner.name(id(&first)), Some("chr1"));
+        assert_eq!(interner.name(id(&second)), Some("chr2"));
+    }
+
+    #[test]
+    fn gfa_version_gates_line_types() {
+        let mut config = GFAParsingConfig::all();
+        assert!(parse_line::<String>(1, b"L\t1\t+\t2\t-\t4M", &config, &mut interner())
+            .unwrap()
+            .is_some());
+        assert!(parse_line::<String>(1, b"E\t*\t1+\t2+\t0\t10\t0\t10\t4M", &config, &mut interner())
+            .unwrap()
+            .is_none());
+
+        config.version = GFAVersion::GFA2;
+        assert!(parse_line::<String>(1, b"L\t1\t+\t2\t-\t4M", &config, &mut interner())
+            .unwrap()
+            .is_none());
+        assert!(parse_line::<String>(1, b"E\t*\t1+\t2+\t0\t10\t0\t10\t4M", &config, &mut interner())
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn reader_detects_gfa2_version_from_header() {
+        let data = b"H\tVN:Z:2.0\nE\t*\t1+\t2+\t0\t10\t0\t10\t4M\nL\t1\t+\t2\t-\t4M\n";
+        let reader = GFAReader::<_, String>::new(Cursor::new(&data[..]), GFAParsingConfig::all());
+        let lines: Vec<_> = reader.map(|l| l.unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(matches!(lines[0], Line::Header(_)));
+        assert!(matches!(lines[1], Line::Edge(_)));
+    }
+
+    #[test]
+    fn parse_edge_line() {
+        let mut config = GFAParsingConfig::all();
+        config.version = GFAVersion::GFA2;
+        let line = parse_line::<String>(
+            1,
+            b"E\t*\t11+\t12-\t0\t10\t0\t10$\t4M",
+            &config,
+            &mut interner(),
+        )
+        .unwrap()
+        .unwrap();
+        match line {
+            Line::Edge(e) => {
+                assert_eq!(e.id, None);
+                assert_eq!(e.sid1, "11");
+                assert_eq!(e.sid1_orient, Orientation::Forward);
+                assert_eq!(e.sid2, "12");
+                assert_eq!(e.sid2_orient, Orientation::Backward);
+                assert_eq!(e.end2, Position { pos: 10, is_end: true });
+            }
+            other => panic!("expected Edge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_gap_line() {
+        let mut config = GFAParsingConfig::all();
+        config.version = GFAVersion::GFA2;
+        let line = parse_line::<String>(1, b"G\t*\t11+\t12-\t500\t*", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::Gap(g) => {
+                assert_eq!(g.sid1, "11");
+                assert_eq!(g.sid2, "12");
+                assert_eq!(g.distance, 500);
+                assert_eq!(g.variance, None);
+            }
+            other => panic!("expected Gap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_fragment_line() {
+        let mut config = GFAParsingConfig::all();
+        config.version = GFAVersion::GFA2;
+        let line = parse_line::<String>(1, b"F\t11\tread1\t0\t10\t0\t10\t4M", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::Fragment(f) => {
+                assert_eq!(f.sid, "11");
+                assert_eq!(f.external_id, "read1");
+                assert_eq!(f.sbeg, Position { pos: 0, is_end: false });
+            }
+            other => panic!("expected Fragment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_ordered_group_line() {
+        let mut config = GFAParsingConfig::all();
+        config.version = GFAVersion::GFA2;
+        let line = parse_line::<String>(1, b"O\tpath1\t11+ 12-", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::OrderedGroup(g) => {
+                assert_eq!(g.id, Some("path1".to_string()));
+                assert_eq!(
+                    g.items,
+                    vec![
+                        ("11".to_string(), Orientation::Forward),
+                        ("12".to_string(), Orientation::Backward),
+                    ]
+                );
+            }
+            other => panic!("expected OrderedGroup, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_unordered_group_line() {
+        let mut config = GFAParsingConfig::all();
+        config.version = GFAVersion::GFA2;
+        let line = parse_line::<String>(1, b"U\tset1\t11 12", &config, &mut interner())
+            .unwrap()
+            .unwrap();
+        match line {
+            Line::UnorderedGroup(g) => {
+                assert_eq!(g.id, Some("set1".to_string()));
+                assert_eq!(g.items, vec!["11".to_string(), "12".to_string()]);
+            }
+            other => panic!("expected UnorderedGroup, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reader_lenient_tolerance_fills_defaults_and_keeps_the_line() {
+        let data = b"S\t1\tACGT\nL\tnot-a-link\nS\t2\tTTTT\n";
+        let mut config = GFAParsingConfig::all();
+        config.tolerance = ParserTolerance::Lenient;
+        let reader = GFAReader::<_, String>::new(Cursor::new(&data[..]), config);
+        let lines: Vec<_> = reader.map(|l| l.unwrap()).collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(matches!(lines[0], Line::Segment(_)));
+        match &lines[1] {
+            Line::Link(l) => {
+                assert_eq!(l.from_orient, Orientation::Forward);
+                assert_eq!(l.to_orient, Orientation::Forward);
+            }
+            other => panic!("expected Link, got {:?}", other),
+        }
+        assert!(matches!(lines[2], Line::Segment(_)));
+    }
+
+    #[test]
+    fn interned_ids_never_collide_with_literal_numeric_segment_ids() {
+        let config = GFAParsingConfig::all();
+        let mut interner = interner();
+
+        let literal = parse_line::<usize>(1, b"S\t0\tACGT", &config, &mut interner)
+            .unwrap()
+            .unwrap();
+        let named = parse_line::<usize>(2, b"S\tchr1\tTTTT", &config, &mut interner)
+            .unwrap()
+            .unwrap();
+
+        let id = |line: &Line<usize>| match line {
+            Line::Segment(s) => s.name,
+            other => panic!("expected Segment, got {:?}", other),
+        };
+
+        assert_ne!(id(&literal), id(&named));
+    }
+
+    #[test]
+    fn reader_streams_lines_and_skips_comments() {
+        let data = b"H\tVN:Z:1.0\n#a comment\nS\t1\tACGT\nS\t2\tTTTT\n";
+        let reader = GFAReader::<_, String>::new(Cursor::new(&data[..]), GFAParsingConfig::all());
+        let lines: Vec<_> = reader.map(|l| l.unwrap()).collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(matches!(lines[0], Line::Header(_)));
+        assert!(matches!(lines[1], Line::Segment(_)));
+        assert!(matches!(lines[2], Line::Segment(_)));
+    }
+
+    #[test]
+    fn reader_safe_tolerance_skips_malformed_lines() {
+        let data = b"S\t1\tACGT\nL\tnot-a-link\nS\t2\tTTTT\n";
+        let mut config = GFAParsingConfig::all();
+        config.tolerance = ParserTolerance::Safe;
+        let reader = GFAReader::<_, String>::new(Cursor::new(&data[..]), config);
+        let lines: Vec<_> = reader.map(|l| l.unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(matches!(lines[0], Line::Segment(_)));
+        assert!(matches!(lines[1], Line::Segment(_)));
+    }
+
+    #[test]
+    fn reader_pedantic_tolerance_surfaces_errors() {
+        let data = b"S\t1\tACGT\nL\tnot-a-link\n";
+        let reader = GFAReader::<_, String>::new(Cursor::new(&data[..]), GFAParsingConfig::all());
+        let results: Vec<_> = reader.collect();
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    struct FailingReader {
+        data: &'static [u8],
+        pos: usize,
+    }
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pos >= self.data.len() {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "disk fell over"));
+            }
+            let n = buf.len().min(self.data.len() - self.pos);
+            buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn mid_stream_io_error_is_not_mistaken_for_eof() {
+        let source = std::io::BufReader::new(FailingReader {
+            data: b"S\t1\tACGT\n",
+            pos: 0,
+        });
+        let mut reader = GFAReader::<_, String>::new(source, GFAParsingConfig::all());
+
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next() {
+            Some(Err(ParseError::Io(_))) => {}
+            other => panic!("expected an Io error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::interner::SegmentInterner;
+
+/// Types that can serve as a segment identifier in [`crate::gfa::Segment`],
+/// [`crate::gfa::Link`], [`crate::gfa::Path`] and [`crate::gfa::GFA`].
+///
+/// Implemented for `String` (the default, matching segment names as they
+/// appear in the file) and for `usize`/`u64` (for GFAs whose segment names
+/// are numeric, as produced by tools like `vg` and `odgi`). Using a `Copy`
+/// integer id instead of an owned `String` avoids a heap allocation per
+/// segment reference, which matters once a graph has millions of them.
+pub trait SegmentId: Clone + Debug + Default + Eq + Hash + Ord {
+    /// Parses a segment name as it appears in a GFA file into this id type,
+    /// or `None` if it doesn't fit (e.g. a non-numeric name for `usize`).
+    /// Intended for builders that work with already-numeric names; see
+    /// [`SegmentId::intern`] for names of unknown shape.
+    fn try_parse_name(s: &str) -> Option<Self>;
+
+    /// Parses a segment name, panicking if it doesn't fit this id type.
+    /// Intended for tests and hand-built graphs; fallible parsers should use
+    /// [`SegmentId::try_parse_name`] or [`SegmentId::intern`] instead.
+    fn parse_name(s: &str) -> Self {
+        Self::try_parse_name(s).expect("invalid segment name")
+    }
+
+    /// Converts a raw segment name from a parsed file into this id type.
+    /// Unlike `try_parse_name`, this never fails: a name that already fits
+    /// `Self` (e.g. a numeric name for `usize`) keeps its literal value, and
+    /// anything else is assigned an id via `interner`, so arbitrary segment
+    /// names can still be used with `usize`/`u64` graphs. `String` ignores
+    /// `interner` and keeps the name verbatim. `interner`'s ids are tagged to
+    /// stay disjoint from literal numeric names; see its doc comment.
+    fn intern(name: &str, interner: &mut SegmentInterner) -> Self;
+}
+
+impl SegmentId for String {
+    fn try_parse_name(s: &str) -> Option<Self> {
+        Some(s.to_string())
+    }
+
+    fn intern(name: &str, _interner: &mut SegmentInterner) -> Self {
+        name.to_string()
+    }
+}
+
+impl SegmentId for usize {
+    fn try_parse_name(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    fn intern(name: &str, interner: &mut SegmentInterner) -> Self {
+        Self::try_parse_name(name).unwrap_or_else(|| interner.intern(name))
+    }
+}
+
+impl SegmentId for u64 {
+    fn try_parse_name(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    fn intern(name: &str, interner: &mut SegmentInterner) -> Self {
+        Self::try_parse_name(name).unwrap_or_else(|| interner.intern(name) as u64)
+    }
+}